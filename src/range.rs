@@ -5,7 +5,7 @@
 
 use iter::*;
 use iter::internal::*;
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 
 pub struct RangeIter<T> {
     range: Range<T>,
@@ -99,10 +99,15 @@ macro_rules! indexed_range_impl {
 macro_rules! unindexed_range_impl {
     ( $t:ty ) => {
         impl RangeIter<$t> {
-            fn len(&self) -> u64 {
+            // `u64` is too narrow to hold the length of a `u128`/`i128` range
+            // without truncating, so the length is computed in `u128` here.
+            // This macro is only instantiated for `u128`/`i128`: for a narrower
+            // signed `$t`, the `as u128` cast would sign-extend a wrapped
+            // difference and give a wrong length.
+            fn len(&self) -> u128 {
                 let Range { start, end } = self.range;
                 if end > start {
-                    end.wrapping_sub(start) as u64
+                    end.wrapping_sub(start) as u128
                 } else {
                     0
                 }
@@ -143,6 +148,118 @@ macro_rules! unindexed_range_impl {
     }
 }
 
+// Like `indexed_range_impl!`, but for `$t` wider than `usize` on some
+// targets: length/`split_at` work in `u64`, converting down to `usize` with
+// a checked assertion; `drive_unindexed` falls back to the unindexed route
+// when the range is too long to index.
+macro_rules! indexed_wide_range_impl {
+    ( $t:ty ) => {
+        impl RangeIter<$t> {
+            fn len(&self) -> u64 {
+                let Range { start, end } = self.range;
+                if end > start {
+                    end.wrapping_sub(start) as u64
+                } else {
+                    0
+                }
+            }
+        }
+
+        impl ParallelIterator for RangeIter<$t> {
+            type Item = $t;
+
+            fn drive_unindexed<C>(self, consumer: C) -> C::Result
+                where C: UnindexedConsumer<Self::Item>
+            {
+                if RangeIter::len(&self) <= ::std::usize::MAX as u64 {
+                    bridge(self, consumer)
+                } else {
+                    bridge_unindexed(self, consumer)
+                }
+            }
+
+            fn opt_len(&mut self) -> Option<usize> {
+                let len = RangeIter::len(self);
+                if len <= ::std::usize::MAX as u64 {
+                    Some(len as usize)
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl BoundedParallelIterator for RangeIter<$t> {
+            fn upper_bound(&mut self) -> usize {
+                ExactParallelIterator::len(self)
+            }
+
+            fn drive<C>(self, consumer: C) -> C::Result
+                where C: Consumer<Self::Item>
+            {
+                bridge(self, consumer)
+            }
+        }
+
+        impl ExactParallelIterator for RangeIter<$t> {
+            fn len(&mut self) -> usize {
+                let len = RangeIter::len(self);
+                assert!(len <= ::std::usize::MAX as u64,
+                        "range has more than `usize::MAX` elements, which is too many to \
+                         index; use a plain `par_iter()` without `zip`/`enumerate`/etc. instead");
+                len as usize
+            }
+        }
+
+        impl IndexedParallelIterator for RangeIter<$t> {
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+                where CB: ProducerCallback<Self::Item>
+            {
+                callback.callback(self)
+            }
+        }
+
+        impl Producer for RangeIter<$t> {
+            type Item = <Range<$t> as Iterator>::Item;
+            type IntoIter = Range<$t>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.range
+            }
+
+            fn split_at(self, index: usize) -> (Self, Self) {
+                assert!(index as u64 <= RangeIter::len(&self));
+                // For signed $t, the length and requested index could be greater than $t::MAX, and
+                // then `index as $t` could wrap to negative, so wrapping_add is necessary.
+                let mid = self.range.start.wrapping_add(index as $t);
+                let left = self.range.start .. mid;
+                let right = mid .. self.range.end;
+                (RangeIter { range: left }, RangeIter { range: right })
+            }
+        }
+
+        impl UnindexedProducer for RangeIter<$t> {
+            type Item = $t;
+
+            fn split(mut self) -> (Self, Option<Self>) {
+                let index = RangeIter::len(&self) / 2;
+                if index > 0 {
+                    let mid = self.range.start.wrapping_add(index as $t);
+                    let right = mid .. self.range.end;
+                    self.range.end = mid;
+                    (self, Some(RangeIter { range: right }))
+                } else {
+                    (self, None)
+                }
+            }
+
+            fn fold_with<F>(self, folder: F) -> F
+                where F: Folder<Self::Item>
+            {
+                folder.consume_iter(self)
+            }
+        }
+    }
+}
+
 // all Range<T> with ExactSizeIterator
 indexed_range_impl!{u8}
 indexed_range_impl!{u16}
@@ -153,6 +270,383 @@ indexed_range_impl!{i16}
 indexed_range_impl!{i32}
 indexed_range_impl!{isize}
 
-// other Range<T> with just Iterator
-unindexed_range_impl!{u64}
-unindexed_range_impl!{i64}
+// `u64`/`i64` are wider than `usize` on 32-bit targets, so they get the
+// checked, possibly-falls-back-to-unindexed treatment instead of the plain
+// `indexed_range_impl!`.
+indexed_wide_range_impl!{u64}
+indexed_wide_range_impl!{i64}
+
+// 128-bit ranges have no indexed treatment at all (there's no way to check
+// their length against `usize` the way `indexed_wide_range_impl!` does for
+// `u64`/`i64`, since a 128-bit range can be far longer than `u64::MAX`), so
+// they just get `ParallelIterator` via the unindexed route.
+unindexed_range_impl!{u128}
+unindexed_range_impl!{i128}
+
+/// `RangeInclusiveIter` is the parallel iterator type for inclusive ranges
+/// (`RangeInclusive<T>`); this is the type for values created by an `a..=b`
+/// expression.
+///
+/// To dodge the overflow that would occur when `end` is `T::max_value()`
+/// (there is no exclusive-range equivalent of `end + 1`), the very last
+/// element of the range is split off and tracked separately in `last`; the
+/// rest of the range is represented exactly like `RangeIter<T>`, i.e. as a
+/// plain exclusive `Range<T>`.
+pub struct RangeInclusiveIter<T> {
+    range: Range<T>,
+    last: Option<T>,
+}
+
+impl<T> IntoParallelIterator for RangeInclusive<T>
+    where RangeInclusiveIter<T>: ParallelIterator,
+          T: Copy + PartialOrd
+{
+    type Item = <RangeInclusiveIter<T> as ParallelIterator>::Item;
+    type Iter = RangeInclusiveIter<T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        let (start, end) = self.into_inner();
+        if start > end {
+            RangeInclusiveIter { range: start..start, last: None }
+        } else {
+            RangeInclusiveIter { range: start..end, last: Some(end) }
+        }
+    }
+}
+
+impl<T> IntoIterator for RangeInclusiveIter<T>
+    where Range<T>: Iterator<Item = T>
+{
+    type Item = T;
+    type IntoIter = ::std::iter::Chain<Range<T>, ::std::option::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.range.chain(self.last)
+    }
+}
+
+macro_rules! indexed_range_inclusive_impl {
+    ( $t:ty ) => {
+        impl ParallelIterator for RangeInclusiveIter<$t> {
+            type Item = $t;
+
+            fn drive_unindexed<C>(self, consumer: C) -> C::Result
+                where C: UnindexedConsumer<Self::Item>
+            {
+                bridge(self, consumer)
+            }
+
+            fn opt_len(&mut self) -> Option<usize> {
+                Some(ExactParallelIterator::len(self))
+            }
+        }
+
+        impl BoundedParallelIterator for RangeInclusiveIter<$t> {
+            fn upper_bound(&mut self) -> usize {
+                ExactParallelIterator::len(self)
+            }
+
+            fn drive<C>(self, consumer: C) -> C::Result
+                where C: Consumer<Self::Item>
+            {
+                bridge(self, consumer)
+            }
+        }
+
+        impl ExactParallelIterator for RangeInclusiveIter<$t> {
+            fn len(&mut self) -> usize {
+                self.range.len() + if self.last.is_some() { 1 } else { 0 }
+            }
+        }
+
+        impl IndexedParallelIterator for RangeInclusiveIter<$t> {
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+                where CB: ProducerCallback<Self::Item>
+            {
+                callback.callback(self)
+            }
+        }
+
+        impl Producer for RangeInclusiveIter<$t> {
+            type Item = $t;
+            type IntoIter = <Self as IntoIterator>::IntoIter;
+
+            fn into_iter(self) -> Self::IntoIter {
+                IntoIterator::into_iter(self)
+            }
+
+            fn split_at(self, index: usize) -> (Self, Self) {
+                let RangeInclusiveIter { range, last } = self;
+                let range_len = range.len();
+                assert!(index <= range_len + if last.is_some() { 1 } else { 0 });
+                if index <= range_len {
+                    // For signed $t, the length and requested index could be greater than
+                    // $t::MAX, and then `index as $t` could wrap to negative, so
+                    // wrapping_add is necessary.
+                    let mid = range.start.wrapping_add(index as $t);
+                    let left = range.start .. mid;
+                    let right = mid .. range.end;
+                    (RangeInclusiveIter { range: left, last: None },
+                     RangeInclusiveIter { range: right, last: last })
+                } else {
+                    // The split point falls right after the extra inclusive element,
+                    // which only happens when `index == len()`; everything, including
+                    // `last`, goes to the left and the right half is empty.
+                    let end = range.end;
+                    (RangeInclusiveIter { range: range, last: last },
+                     RangeInclusiveIter { range: end..end, last: None })
+                }
+            }
+        }
+    }
+}
+
+macro_rules! unindexed_range_inclusive_impl {
+    ( $t:ty ) => {
+        impl RangeInclusiveIter<$t> {
+            // Computed in `u128` so that the `+ 1` for `last` cannot overflow,
+            // even for `MIN..=MAX`; the cast goes through `u64` first so a
+            // wrapped signed difference is not sign-extended.
+            fn len(&self) -> u128 {
+                let Range { start, end } = self.range;
+                let len = if end > start { end.wrapping_sub(start) as u64 as u128 } else { 0 };
+                len + if self.last.is_some() { 1 } else { 0 }
+            }
+        }
+
+        impl ParallelIterator for RangeInclusiveIter<$t> {
+            type Item = $t;
+
+            fn drive_unindexed<C>(self, consumer: C) -> C::Result
+                where C: UnindexedConsumer<Self::Item>
+            {
+                bridge_unindexed(self, consumer)
+            }
+        }
+
+        impl UnindexedProducer for RangeInclusiveIter<$t> {
+            type Item = $t;
+
+            fn split(mut self) -> (Self, Option<Self>) {
+                let index = self.len() / 2;
+                if index == 0 {
+                    return (self, None);
+                }
+                let range_len = if self.range.end > self.range.start {
+                    self.range.end.wrapping_sub(self.range.start) as u64 as u128
+                } else {
+                    0
+                };
+                if index <= range_len {
+                    let mid = self.range.start.wrapping_add(index as $t);
+                    let right = RangeInclusiveIter { range: mid..self.range.end, last: self.last.take() };
+                    self.range.end = mid;
+                    (self, Some(right))
+                } else {
+                    // Splitting off just the trailing inclusive element.
+                    let end = self.range.end;
+                    let right = RangeInclusiveIter { range: end..end, last: self.last.take() };
+                    (self, Some(right))
+                }
+            }
+
+            fn fold_with<F>(self, folder: F) -> F
+                where F: Folder<Self::Item>
+            {
+                folder.consume_iter(self)
+            }
+        }
+    }
+}
+
+// all RangeInclusive<T> with ExactSizeIterator Range<T>
+indexed_range_inclusive_impl!{u8}
+indexed_range_inclusive_impl!{u16}
+indexed_range_inclusive_impl!{u32}
+indexed_range_inclusive_impl!{usize}
+indexed_range_inclusive_impl!{i8}
+indexed_range_inclusive_impl!{i16}
+indexed_range_inclusive_impl!{i32}
+indexed_range_inclusive_impl!{isize}
+
+// other RangeInclusive<T> with just Iterator
+unindexed_range_inclusive_impl!{u64}
+unindexed_range_inclusive_impl!{i64}
+
+/// `StepRangeIter` is the parallel iterator type for a strided range, as
+/// produced by `RangeIter::step_by`.
+///
+/// `end` always holds the original, valid range endpoint rather than a
+/// recomputed `start + len * step` (which can overflow `$t`); `into_iter`
+/// relies on `len`/`take` to stop at the right place instead.
+pub struct StepRangeIter<T> {
+    start: T,
+    end: T,
+    step: usize,
+    len: usize,
+}
+
+macro_rules! indexed_step_range_impl {
+    ( $t:ty ) => {
+        impl RangeIter<$t> {
+            /// Parallelizes a strided range, visiting `start, start + step,
+            /// start + 2 * step, ...` instead of every integer in the
+            /// range, the same way `Iterator::step_by` does for a
+            /// sequential range.
+            pub fn step_by(self, step: usize) -> StepRangeIter<$t> {
+                assert!(step != 0, "step_by requires a non-zero step");
+                let len = self.range.len();
+                // `ceil(len / step)`, matching `Iterator::step_by`'s count.
+                let steps = if len == 0 { 0 } else { (len - 1) / step + 1 };
+                StepRangeIter { start: self.range.start, end: self.range.end, step: step, len: steps }
+            }
+        }
+
+        impl ParallelIterator for StepRangeIter<$t> {
+            type Item = $t;
+
+            fn drive_unindexed<C>(self, consumer: C) -> C::Result
+                where C: UnindexedConsumer<Self::Item>
+            {
+                bridge(self, consumer)
+            }
+
+            fn opt_len(&mut self) -> Option<usize> {
+                Some(self.len)
+            }
+        }
+
+        impl BoundedParallelIterator for StepRangeIter<$t> {
+            fn upper_bound(&mut self) -> usize {
+                ExactParallelIterator::len(self)
+            }
+
+            fn drive<C>(self, consumer: C) -> C::Result
+                where C: Consumer<Self::Item>
+            {
+                bridge(self, consumer)
+            }
+        }
+
+        impl ExactParallelIterator for StepRangeIter<$t> {
+            fn len(&mut self) -> usize {
+                self.len
+            }
+        }
+
+        impl IndexedParallelIterator for StepRangeIter<$t> {
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+                where CB: ProducerCallback<Self::Item>
+            {
+                callback.callback(self)
+            }
+        }
+
+        impl Producer for StepRangeIter<$t> {
+            type Item = $t;
+            type IntoIter = ::std::iter::Take<::std::iter::StepBy<Range<$t>>>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                // `self.end` is just the original range bound; `take(self.len)`
+                // is what actually stops the iterator at the right element.
+                (self.start .. self.end).step_by(self.step).take(self.len)
+            }
+
+            fn split_at(self, index: usize) -> (Self, Self) {
+                assert!(index <= self.len);
+                if index == self.len {
+                    // Right half is empty; avoid computing `start + len * step`,
+                    // which can overflow `$t` even though every real element fits.
+                    let left = StepRangeIter { start: self.start, end: self.end, step: self.step, len: self.len };
+                    let right = StepRangeIter { start: self.end, end: self.end, step: self.step, len: 0 };
+                    (left, right)
+                } else {
+                    // `index < self.len`, so this is a real element, < `self.end`.
+                    let mid = self.start.wrapping_add((index * self.step) as $t);
+                    let left = StepRangeIter { start: self.start, end: self.end, step: self.step, len: index };
+                    let right = StepRangeIter { start: mid, end: self.end, step: self.step, len: self.len - index };
+                    (left, right)
+                }
+            }
+        }
+    }
+}
+
+// strided ranges, for the same types that support plain indexed ranges
+indexed_step_range_impl!{u8}
+indexed_step_range_impl!{u16}
+indexed_step_range_impl!{u32}
+indexed_step_range_impl!{usize}
+indexed_step_range_impl!{i8}
+indexed_step_range_impl!{i16}
+indexed_step_range_impl!{i32}
+indexed_step_range_impl!{isize}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // `step_by` near `$t::MAX` is exactly where a naive `start + len * step`
+    // upper bound overflows and silently drops elements; check it against
+    // the sequential `Iterator::step_by` for a few cases that hug the edge
+    // of the type's range.
+    macro_rules! step_by_matches_sequential {
+        ( $name:ident, $t:ty, $start:expr, $end:expr, $step:expr ) => {
+            #[test]
+            fn $name() {
+                let start: $t = $start;
+                let end: $t = $end;
+                let step: usize = $step;
+                let par: Vec<$t> = (start..end).into_par_iter().step_by(step).collect();
+                let seq: Vec<$t> = (start..end).step_by(step).collect();
+                assert_eq!(par, seq);
+            }
+        }
+    }
+
+    step_by_matches_sequential!{u8_near_max, u8, 0, 200, 128}
+    step_by_matches_sequential!{u8_single_leaf_near_max, u8, 128, 200, 128}
+    step_by_matches_sequential!{u8_full_range, u8, 0, u8::max_value(), 1}
+    step_by_matches_sequential!{u8_step_larger_than_remainder, u8, 250, 255, 3}
+    step_by_matches_sequential!{i8_near_min_and_max, i8, -128, 120, 100}
+    step_by_matches_sequential!{usize_near_max, usize, usize::max_value() - 200, usize::max_value(), 128}
+    step_by_matches_sequential!{empty_range, u8, 10, 10, 5}
+
+    // `RangeInclusiveIter` splits off the trailing element to dodge overflow
+    // at `T::MAX`, so check it against the sequential `RangeInclusive` for
+    // the edge cases that arithmetic has to get right: the last two values
+    // of the type, a single-element range, and an empty (`start > end`) one.
+    macro_rules! range_inclusive_matches_sequential {
+        ( $name:ident, $t:ty, $start:expr, $end:expr ) => {
+            #[test]
+            fn $name() {
+                let start: $t = $start;
+                let end: $t = $end;
+                let par: Vec<$t> = (start..=end).into_par_iter().collect();
+                let seq: Vec<$t> = (start..=end).collect();
+                assert_eq!(par, seq);
+            }
+        }
+    }
+
+    range_inclusive_matches_sequential!{u8_last_two, u8, u8::max_value() - 1, u8::max_value()}
+    range_inclusive_matches_sequential!{u8_single_element, u8, 7, 7}
+    range_inclusive_matches_sequential!{u8_empty, u8, 7, 6}
+    range_inclusive_matches_sequential!{u64_last_two, u64, u64::max_value() - 1, u64::max_value()}
+    range_inclusive_matches_sequential!{u64_single_element, u64, 7, 7}
+    range_inclusive_matches_sequential!{u64_empty, u64, 7, 6}
+
+    // Drives `indexed_wide_range_impl!`'s `u64` length/`split_at` path with
+    // a range whose element count sits right at the edge of `usize::MAX`,
+    // rather than the plain `usize`-length path `u32` and friends take.
+    #[test]
+    fn u64_near_usize_max_len_and_split() {
+        let total = ::std::usize::MAX as u64 - 1;
+        let mut producer = (0u64..total).into_par_iter();
+        assert_eq!(ExactParallelIterator::len(&mut producer), total as usize);
+
+        let (left, mut right) = Producer::split_at(producer, 3);
+        assert_eq!(Producer::into_iter(left).collect::<Vec<_>>(), vec![0u64, 1, 2]);
+        assert_eq!(ExactParallelIterator::len(&mut right), (total - 3) as usize);
+    }
+}